@@ -3,6 +3,7 @@
 
 use std::ops::Add;
 use crate::constants::{*};
+use crate::frame::{CanFrame, IdType};
 
 /// A marker type the signals if the filter is for standard or extended filtering.
 #[derive(Debug, PartialEq, Clone)]
@@ -27,8 +28,91 @@ pub trait CanIdFilter: CanIdFilterPrivateMarker {
     fn mask(&self) -> u32; 
     /// Retrieves if the filter is for standard or extended CAN-IDs.
     fn mask_type(&self) -> MaskType;
-    /// Computes the weight, i.e., the number of matching/accepting CAN-IDs. 
+    /// Computes the weight, i.e., the number of matching/accepting CAN-IDs.
     fn weight(&self) -> u32;
+
+    /// Checks, whether the given [`CanFrame`] is accepted by the filter. Returns `false` whenever the
+    /// filter's [`MaskType`] disagrees with the frame's `IdType`, avoiding spurious matches between
+    /// standard and extended traffic; otherwise delegates to [`CanIdFilter::match_can_id`] using the
+    /// frame's CAN-ID.
+    ///
+    /// # Example
+    /// ```
+    /// use cantypes::filter::{StandardCanIdFilter, CanIdFilter};
+    /// use cantypes::frame::{CanFrame, IdType};
+    ///
+    /// let filter = StandardCanIdFilter::from_can_id(0xABC);
+    /// let frame = CanFrame::new_data_frame(0xABC, IdType::Standard, &[1, 2, 3]);
+    /// assert!(filter.match_frame(&frame));
+    /// ```
+    fn match_frame(&self, frame: &CanFrame) -> bool {
+        let (can_id, id_type) = match frame {
+            CanFrame::DataFrame { can_id, id_type, .. } => (*can_id, id_type),
+            CanFrame::FdDataFrame { can_id, id_type, .. } => (*can_id, id_type),
+            CanFrame::RemoteFrame { can_id, id_type, .. } => (*can_id, id_type),
+            CanFrame::ErrorFrame { can_id, id_type } => (*can_id, id_type),
+        };
+
+        let type_matches = matches!(
+            (self.mask_type(), id_type),
+            (MaskType::Standard, IdType::Standard) | (MaskType::Extended, IdType::Extended)
+        );
+
+        type_matches && self.match_can_id(can_id)
+    }
+
+    /// Checks, whether this filter and `other` can ever accept the same CAN-ID, i.e. whether their
+    /// accepted ID sets overlap. Only the bit positions both filters care about (where both masks
+    /// have a 1-bit) are compared; positions either filter treats as don't-care can never cause a
+    /// mismatch.
+    ///
+    /// # Example
+    /// ```
+    /// use cantypes::filter::{StandardCanIdFilter, CanIdFilter};
+    ///
+    /// let f1 = StandardCanIdFilter::from_can_id(0x100);
+    /// let f2 = StandardCanIdFilter::from_can_id(0x100) + StandardCanIdFilter::from_can_id(0x101);
+    /// assert!(f1.overlaps(&f2));
+    /// ```
+    fn overlaps<O: CanIdFilter>(&self, other: &O) -> bool {
+        let mask = self.mask() & other.mask();
+        (self.can_id() & mask) == (other.can_id() & mask)
+    }
+
+    /// Lazily enumerates every CAN-ID this filter accepts, in ascending order, by iterating the
+    /// don't-care bit positions of its mask -- the same positions [`CanIdFilter::weight`] counts.
+    /// The iterator stays lazy so it remains usable even for [`StandardCanIdFilter::accept_all`] or
+    /// [`ExtendedCanIdFilter::accept_all`], whose `weight()` is as large as 2^29.
+    ///
+    /// # Example
+    /// ```
+    /// use cantypes::filter::{StandardCanIdFilter, CanIdFilter};
+    ///
+    /// let filter = StandardCanIdFilter::from_can_id(0x7_FE) + StandardCanIdFilter::from_can_id(0x7_FF);
+    /// let ids: Vec<u32> = filter.accepted_ids().collect();
+    /// assert_eq!(ids, vec![0x7_FE, 0x7_FF]);
+    /// ```
+    fn accepted_ids(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        let mask = self.mask();
+        let base = self.can_id() & mask;
+        let bit_length = match self.mask_type() {
+            MaskType::Standard => STANDARD_FRAME_ID_LENGTH,
+            MaskType::Extended => EXTENDED_FRAME_ID_LENGTH,
+        };
+
+        // the don't-care bit positions, ascending -- the same positions weight() counts
+        let free_bits: Vec<u32> = (0..bit_length).filter(|i| mask & (1 << i) == 0).collect();
+
+        Box::new((0u32..(1u32 << free_bits.len())).map(move |combo| {
+            let mut id = base;
+            for (bit_index, &pos) in free_bits.iter().enumerate() {
+                if combo & (1 << bit_index) != 0 {
+                    id |= 1 << pos;
+                }
+            }
+            id
+        }))
+    }
 }
 
 /// The struct for modelling standard filter.
@@ -156,7 +240,11 @@ impl Add<&StandardCanIdFilter> for &StandardCanIdFilter {
     fn add(self, rhs: &StandardCanIdFilter) -> Self::Output {
         let left_can_id_filtered = self.can_id() & self.mask();
         let right_can_id_filtered = rhs.can_id() & rhs.mask();
-        let mask = (!left_can_id_filtered | right_can_id_filtered) & (!right_can_id_filtered | left_can_id_filtered);
+        // a bit stays a "care" bit only if both operands already care about it and agree on its
+        // value; a bit either operand treats as don't-care must remain don't-care, or the result
+        // would stop accepting IDs the operand itself already accepted.
+        let both_care = self.mask() & rhs.mask();
+        let mask = both_care & !(left_can_id_filtered ^ right_can_id_filtered);
 
         StandardCanIdFilter {
             can_id: self.can_id,
@@ -293,7 +381,11 @@ impl Add<&ExtendedCanIdFilter> for &ExtendedCanIdFilter {
     fn add(self, rhs: &ExtendedCanIdFilter) -> Self::Output {
         let left_can_id_filtered = self.can_id() & self.mask();
         let right_can_id_filtered = rhs.can_id() & rhs.mask();
-        let mask = (!left_can_id_filtered | right_can_id_filtered) & (!right_can_id_filtered | left_can_id_filtered);
+        // a bit stays a "care" bit only if both operands already care about it and agree on its
+        // value; a bit either operand treats as don't-care must remain don't-care, or the result
+        // would stop accepting IDs the operand itself already accepted.
+        let both_care = self.mask() & rhs.mask();
+        let mask = both_care & !(left_can_id_filtered ^ right_can_id_filtered);
 
         ExtendedCanIdFilter {
             can_id: self.can_id,
@@ -302,6 +394,102 @@ impl Add<&ExtendedCanIdFilter> for &ExtendedCanIdFilter {
     }
 }
 
+/// Greedily merges `filters` down to at most `max_slots` entries, for acceptance-filter hardware
+/// that only exposes a small fixed number of slots. The combined filter of any two inputs always
+/// accepts everything the originals accepted, so the result is guaranteed to be a superset.
+///
+/// At each step, the pair whose combination lets through the fewest extra IDs is merged first. The
+/// extra cost of merging `a` and `b` is `weight(a + b) - |a ∪ b|`, i.e. the combined filter's
+/// weight minus the number of IDs already accepted by either operand; when `a` and `b` never
+/// [`overlap`](CanIdFilter::overlaps), `|a ∪ b|` is simply `weight(a) + weight(b)`, otherwise the
+/// overlap is subtracted out so the cost can never go negative. The two are replaced with their
+/// sum until `max_slots` remain. Merging stops at a single filter, so a non-empty input with
+/// `max_slots` of `0` yields one filter.
+///
+/// Returns the consolidated filters together with the total extra weight introduced by merging,
+/// so callers can judge whether the resulting false-positive load is acceptable.
+///
+/// # Example
+/// ```
+/// use cantypes::filter::{StandardCanIdFilter, CanIdFilter, consolidate};
+///
+/// let filters = vec![
+///     StandardCanIdFilter::from_can_id(0x100),
+///     StandardCanIdFilter::from_can_id(0x101),
+///     StandardCanIdFilter::from_can_id(0x200),
+/// ];
+/// let (merged, _extra_weight) = consolidate(&filters, 2);
+/// assert_eq!(merged.len(), 2);
+/// ```
+pub fn consolidate<F>(filters: &[F], max_slots: usize) -> (Vec<F>, u32)
+where
+    F: CanIdFilter + Add<F, Output = F> + Clone,
+{
+    let mut bank: Vec<F> = filters.to_vec();
+    let mut extra_weight = 0u32;
+
+    while bank.len() > max_slots && bank.len() >= 2 {
+        let mut best: Option<(usize, usize, u32)> = None;
+
+        for i in 0..bank.len() {
+            for j in (i + 1)..bank.len() {
+                let combined_weight = (bank[i].clone() + bank[j].clone()).weight();
+                let union_weight = union_weight(&bank[i], &bank[j]);
+                let cost = combined_weight.saturating_sub(union_weight);
+
+                if best.is_none_or(|(_, _, best_cost)| cost < best_cost) {
+                    best = Some((i, j, cost));
+                }
+            }
+        }
+
+        let (i, j, cost) = best.expect("bank.len() >= 2 guarantees a pair exists");
+        let merged = bank[i].clone() + bank[j].clone();
+
+        // remove the higher index first so the lower index stays valid
+        bank.remove(j);
+        bank.remove(i);
+        bank.push(merged);
+
+        extra_weight += cost;
+    }
+
+    (bank, extra_weight)
+}
+
+/// The largest number of accepted IDs [`union_weight`] is willing to enumerate to compute an
+/// exact intersection count. Above this, both filters being the wide, fully-overlapping kind
+/// `consolidate`'s O(n²) merge scan is most likely to hit (e.g. two [`ExtendedCanIdFilter::accept_all`]
+/// filters, weight 2^29 each), so the cost is approximated instead of walked bit-by-bit.
+const MAX_INTERSECTION_ENUMERATION: u32 = 1 << 20;
+
+/// Computes `|a ∪ b|`, the number of distinct CAN-IDs accepted by `a` or `b`. Falls back to
+/// enumerating the smaller filter's accepted IDs to count the overlap only when the two filters
+/// actually [`overlap`](CanIdFilter::overlaps); disjoint filters (the common case) are handled
+/// without enumerating anything. When the smaller filter's weight exceeds
+/// [`MAX_INTERSECTION_ENUMERATION`], the intersection is approximated as the smaller filter's full
+/// weight (i.e. the overlap is assumed total) rather than enumerated bit-by-bit, so `consolidate`
+/// can't be made to hang merging wide, overlapping extended filters.
+fn union_weight<A: CanIdFilter, B: CanIdFilter>(a: &A, b: &B) -> u32 {
+    if !a.overlaps(b) {
+        return a.weight() + b.weight();
+    }
+
+    let intersection = if a.weight() <= b.weight() {
+        if a.weight() > MAX_INTERSECTION_ENUMERATION {
+            a.weight()
+        } else {
+            a.accepted_ids().filter(|id| b.match_can_id(*id)).count() as u32
+        }
+    } else if b.weight() > MAX_INTERSECTION_ENUMERATION {
+        b.weight()
+    } else {
+        b.accepted_ids().filter(|id| a.match_can_id(*id)).count() as u32
+    };
+
+    a.weight() + b.weight() - intersection
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,4 +555,109 @@ mod tests {
         let filter = ExtendedCanIdFilter::accept_all();
         assert_eq!(filter.weight(), 0x20_00_00_00);
     }
+
+    #[test]
+    fn match_frame_std_filter_001() {
+        let filter = StandardCanIdFilter::from_can_id(0xABC);
+        let frame = CanFrame::new_data_frame(0xABC, IdType::Standard, &[1, 2, 3]);
+        assert!(filter.match_frame(&frame));
+    }
+
+    #[test]
+    fn match_frame_std_filter_rejects_extended() {
+        let filter = StandardCanIdFilter::from_can_id(0xABC);
+        let frame = CanFrame::new_data_frame(0xABC, IdType::Extended, &[1, 2, 3]);
+        assert!(!filter.match_frame(&frame));
+    }
+
+    #[test]
+    fn match_frame_ext_filter_001() {
+        let filter = ExtendedCanIdFilter::from_can_id(0x1F_FF_CC_FF);
+        let frame = CanFrame::new_data_frame(0x1F_FF_CC_FF, IdType::Extended, &[1, 2, 3]);
+        assert!(filter.match_frame(&frame));
+    }
+
+    #[test]
+    fn match_frame_ext_filter_rejects_standard() {
+        let filter = ExtendedCanIdFilter::from_can_id(0xABC);
+        let frame = CanFrame::new_data_frame(0xABC, IdType::Standard, &[1, 2, 3]);
+        assert!(!filter.match_frame(&frame));
+    }
+
+    #[test]
+    fn consolidate_std_filter_within_budget_is_noop() {
+        let filters = vec![
+            StandardCanIdFilter::from_can_id(0x100),
+            StandardCanIdFilter::from_can_id(0x200),
+        ];
+        let (merged, extra_weight) = consolidate(&filters, 2);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(extra_weight, 0);
+    }
+
+    #[test]
+    fn consolidate_std_filter_merges_down_to_max_slots() {
+        let filters = vec![
+            StandardCanIdFilter::from_can_id(0x100),
+            StandardCanIdFilter::from_can_id(0x101),
+            StandardCanIdFilter::from_can_id(0x200),
+        ];
+        let (merged, _extra_weight) = consolidate(&filters, 2);
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn consolidate_std_filter_result_is_a_superset() {
+        let originals = vec![
+            StandardCanIdFilter::from_can_id(0x100),
+            StandardCanIdFilter::from_can_id(0x101),
+            StandardCanIdFilter::from_can_id(0x200),
+        ];
+        let (merged, _extra_weight) = consolidate(&originals, 1);
+
+        for original in &originals {
+            assert!(merged.iter().any(|m| m.match_can_id(original.can_id())));
+        }
+    }
+
+    #[test]
+    fn overlaps_std_filter_001() {
+        let f1 = StandardCanIdFilter::from_can_id(0x100);
+        let f2 = StandardCanIdFilter::from_can_id(0x100) + StandardCanIdFilter::from_can_id(0x101);
+        assert!(f1.overlaps(&f2));
+    }
+
+    #[test]
+    fn overlaps_std_filter_002() {
+        let f1 = StandardCanIdFilter::from_can_id(0x100);
+        let f2 = StandardCanIdFilter::from_can_id(0x200);
+        assert!(!f1.overlaps(&f2));
+    }
+
+    #[test]
+    fn overlaps_ext_filter_accept_all() {
+        let f1 = ExtendedCanIdFilter::accept_all();
+        let f2 = ExtendedCanIdFilter::from_can_id(0x1F_FF_CC_FF);
+        assert!(f1.overlaps(&f2));
+    }
+
+    #[test]
+    fn accepted_ids_std_filter_single() {
+        let filter = StandardCanIdFilter::from_can_id(0x2BC);
+        let ids: Vec<u32> = filter.accepted_ids().collect();
+        assert_eq!(ids, vec![0x2BC]);
+    }
+
+    #[test]
+    fn accepted_ids_std_filter_combined_is_ascending() {
+        let filter = StandardCanIdFilter::from_can_id(0x7_FE) + StandardCanIdFilter::from_can_id(0x7_FF);
+        let ids: Vec<u32> = filter.accepted_ids().collect();
+        assert_eq!(ids, vec![0x7_FE, 0x7_FF]);
+    }
+
+    #[test]
+    fn accepted_ids_std_filter_matches_weight() {
+        let filter = StandardCanIdFilter::from_can_id(0x7_F0) + StandardCanIdFilter::from_can_id(0x7_FF);
+        assert_eq!(filter.accepted_ids().count() as u32, filter.weight());
+    }
 }
\ No newline at end of file
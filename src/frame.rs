@@ -1,8 +1,19 @@
 //! Module providing CAN frame definitions and traits to easily create, manipulate CAN frames and access their data.
+//!
+//! Both classic CAN 2.0 frames (up to 8 data bytes) and CAN FD frames (up to 64 data bytes) are modelled
+//! through the [`CanFrame`] enum.
 
 use std::fmt::Debug;
 use crate::{EXTENDED_FRAME_ID_MASK, STANDARD_FRAME_ID_MASK};
 
+/// The valid CAN FD data lengths, in ascending order, as defined by the ISO 11898-1 FD DLC encoding.
+const FD_DATA_LENGTHS: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// Snaps an arbitrary data length up to the nearest valid CAN FD data length, capped at 64.
+fn fd_data_len(len: usize) -> u8 {
+    FD_DATA_LENGTHS.into_iter().find(|&l| len <= l as usize).unwrap_or(64)
+}
+
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum IdType {
@@ -20,6 +31,16 @@ pub enum CanFrame {
         dlc: u8,
         data: [u8; 8]
     },
+    FdDataFrame {
+        can_id: u32,
+        id_type: IdType,
+        dlc: u8,
+        /// Bit-rate-switch flag: the payload was transmitted at the higher FD data bit rate.
+        brs: bool,
+        /// Error-state-indicator flag: the transmitter was in the error-passive state.
+        esi: bool,
+        data: [u8; 64]
+    },
     RemoteFrame {
         can_id: u32,
         id_type: IdType,
@@ -42,9 +63,8 @@ impl CanFrame {
 
         // allocate space for the CAN data and copy over from the provided slice
         let mut candata = [0u8; 8];
-        for (cd, d) in candata.as_mut().into_iter().zip(data.into_iter()) {
-            *cd = *d;
-        }
+        let copy_len = data.len().min(candata.len());
+        candata[..copy_len].copy_from_slice(&data[..copy_len]);
 
         // limit the dlc to the interval [0,8]
         let dlc = if data.len() > 8 {
@@ -57,6 +77,25 @@ impl CanFrame {
         CanFrame::DataFrame { can_id: canid, id_type, dlc, data: candata }
     }
 
+    pub fn new_fd_data_frame(can_id: u32, id_type: IdType, brs: bool, esi: bool, data: &[u8]) -> CanFrame {
+        // mask CAN-Id based on the ID type
+        let canid = can_id & match id_type {
+            IdType::Standard => STANDARD_FRAME_ID_MASK,
+            IdType::Extended => EXTENDED_FRAME_ID_MASK,
+        };
+
+        // allocate space for the CAN FD data and copy over from the provided slice
+        let mut candata = [0u8; 64];
+        let copy_len = data.len().min(candata.len());
+        candata[..copy_len].copy_from_slice(&data[..copy_len]);
+
+        // snap the dlc to the nearest valid CAN FD data length in [0,64]
+        let dlc = fd_data_len(data.len());
+
+        // construct the FD data frame
+        CanFrame::FdDataFrame { can_id: canid, id_type, dlc, brs, esi, data: candata }
+    }
+
     pub fn new_remote_frame(can_id: u32, id_type: IdType, dlc: u8) -> CanFrame {
         let canid = can_id & match id_type {
             IdType::Standard => STANDARD_FRAME_ID_MASK,
@@ -73,6 +112,90 @@ impl CanFrame {
         // construct the data frame
         CanFrame::RemoteFrame { can_id: canid, id_type, dlc }
     }
+
+    /// Sets the CAN-ID of this frame in place, re-masking it according to the frame's current [`IdType`].
+    pub fn set_can_id(&mut self, can_id: u32) {
+        let (id, id_type) = match self {
+            CanFrame::DataFrame { can_id, id_type, .. } => (can_id, &*id_type),
+            CanFrame::FdDataFrame { can_id, id_type, .. } => (can_id, &*id_type),
+            CanFrame::RemoteFrame { can_id, id_type, .. } => (can_id, &*id_type),
+            CanFrame::ErrorFrame { can_id, id_type } => (can_id, &*id_type),
+        };
+
+        let mask = match id_type {
+            IdType::Standard => STANDARD_FRAME_ID_MASK,
+            IdType::Extended => EXTENDED_FRAME_ID_MASK,
+        };
+
+        *id = can_id & mask;
+    }
+
+    /// Sets the [`IdType`] of this frame in place, re-masking the already-stored CAN-ID to the new type's width.
+    pub fn set_id_type(&mut self, id_type: IdType) {
+        let can_id = match self {
+            CanFrame::DataFrame { can_id, .. } => can_id,
+            CanFrame::FdDataFrame { can_id, .. } => can_id,
+            CanFrame::RemoteFrame { can_id, .. } => can_id,
+            CanFrame::ErrorFrame { can_id, .. } => can_id,
+        };
+
+        let mask = match id_type {
+            IdType::Standard => STANDARD_FRAME_ID_MASK,
+            IdType::Extended => EXTENDED_FRAME_ID_MASK,
+        };
+
+        *can_id &= mask;
+
+        match self {
+            CanFrame::DataFrame { id_type: t, .. } => *t = id_type,
+            CanFrame::FdDataFrame { id_type: t, .. } => *t = id_type,
+            CanFrame::RemoteFrame { id_type: t, .. } => *t = id_type,
+            CanFrame::ErrorFrame { id_type: t, .. } => *t = id_type,
+        }
+    }
+
+    /// Returns a mutable view of the `dlc`-length data slice, for [`CanFrame::DataFrame`] and
+    /// [`CanFrame::FdDataFrame`]. Returns `None` for remote and error frames, which carry no data.
+    pub fn data_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            CanFrame::DataFrame { dlc, data, .. } => Some(&mut data[..*dlc as usize]),
+            CanFrame::FdDataFrame { dlc, data, .. } => Some(&mut data[..*dlc as usize]),
+            CanFrame::RemoteFrame { .. } | CanFrame::ErrorFrame { .. } => None,
+        }
+    }
+
+    /// Overwrites the payload of a [`CanFrame::DataFrame`] or [`CanFrame::FdDataFrame`] in place, updating
+    /// the buffer and `dlc` with the same clamping logic as [`CanFrame::new_data_frame`] and
+    /// [`CanFrame::new_fd_data_frame`]. Does nothing for remote and error frames.
+    pub fn set_data(&mut self, data: &[u8]) {
+        match self {
+            CanFrame::DataFrame { dlc, data: buf, .. } => {
+                let mut candata = [0u8; 8];
+                let copy_len = data.len().min(candata.len());
+                candata[..copy_len].copy_from_slice(&data[..copy_len]);
+
+                *dlc = if data.len() > 8 { 8 } else { data.len() as u8 };
+                *buf = candata;
+            },
+            CanFrame::FdDataFrame { dlc, data: buf, .. } => {
+                let mut candata = [0u8; 64];
+                let copy_len = data.len().min(candata.len());
+                candata[..copy_len].copy_from_slice(&data[..copy_len]);
+
+                *dlc = fd_data_len(data.len());
+                *buf = candata;
+            },
+            CanFrame::RemoteFrame { .. } | CanFrame::ErrorFrame { .. } => {},
+        }
+    }
+
+    /// Sets the `dlc` of a [`CanFrame::RemoteFrame`] in place, clamped to the interval `[0,8]`. Does
+    /// nothing for other frame kinds.
+    pub fn set_dlc(&mut self, dlc: u8) {
+        if let CanFrame::RemoteFrame { dlc: d, .. } = self {
+            *d = if dlc > 8 { 8u8 } else { dlc };
+        }
+    }
 }
 
 impl Debug for CanFrame {
@@ -90,6 +213,20 @@ impl Debug for CanFrame {
                 debugstruct.field("data", &format_args!("{:02X?}", &data.as_slice()[..dlc]));
                 debugstruct.finish()
             },
+            CanFrame::FdDataFrame { can_id, id_type, dlc, brs, esi, data } => {
+                let dlc = *dlc as usize;
+                let mut debugstruct = f.debug_struct("FdDataFrame");
+                match id_type {
+                    IdType::Standard => debugstruct.field("can_id", &format_args!("{:#03X}", can_id)),
+                    IdType::Extended => debugstruct.field("can_id", &format_args!("{:#08X}", can_id)),
+                };
+                debugstruct.field("id_type", id_type);
+                debugstruct.field("dlc", &dlc);
+                debugstruct.field("brs", brs);
+                debugstruct.field("esi", esi);
+                debugstruct.field("data", &format_args!("{:02X?}", &data.as_slice()[..dlc]));
+                debugstruct.finish()
+            },
             CanFrame::RemoteFrame { can_id, id_type, dlc } => {
                 let dlc = *dlc as usize;
                 let mut debugstruct = f.debug_struct("Remote");
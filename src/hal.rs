@@ -0,0 +1,131 @@
+//! Module providing an optional [`embedded-hal`](embedded_hal) integration so [`CanFrame`] values
+//! can flow directly through generic, HAL-based CAN drivers.
+//!
+//! Enabled via the `embedded-hal` feature.
+
+use embedded_hal::can::{ExtendedId, Frame, Id, StandardId};
+
+use crate::frame::{CanFrame, IdType};
+
+impl CanFrame {
+    /// Maps this frame's CAN-ID onto an [`embedded_hal::can::Id`], respecting its [`IdType`].
+    ///
+    /// # Example
+    /// ```
+    /// use cantypes::frame::{CanFrame, IdType};
+    /// use embedded_hal::can::Id;
+    ///
+    /// let frame = CanFrame::new_data_frame(0xABC, IdType::Standard, &[1, 2, 3]);
+    /// assert!(matches!(frame.hal_id(), Id::Standard(_)));
+    /// ```
+    pub fn hal_id(&self) -> Id {
+        let (can_id, id_type) = match self {
+            CanFrame::DataFrame { can_id, id_type, .. } => (*can_id, id_type),
+            CanFrame::FdDataFrame { can_id, id_type, .. } => (*can_id, id_type),
+            CanFrame::RemoteFrame { can_id, id_type, .. } => (*can_id, id_type),
+            CanFrame::ErrorFrame { can_id, id_type } => (*can_id, id_type),
+        };
+
+        match id_type {
+            IdType::Standard => Id::Standard(StandardId::new(can_id as u16).unwrap()),
+            IdType::Extended => Id::Extended(ExtendedId::new(can_id).unwrap()),
+        }
+    }
+
+    /// Returns `true` if this frame carries an [`IdType::Extended`] CAN-ID.
+    pub fn is_extended(&self) -> bool {
+        matches!(self.hal_id(), Id::Extended(_))
+    }
+
+    /// Returns `true` if this frame is a [`CanFrame::RemoteFrame`].
+    pub fn is_remote_frame(&self) -> bool {
+        matches!(self, CanFrame::RemoteFrame { .. })
+    }
+
+    /// Returns the frame's CAN-ID as an [`embedded_hal::can::Id`]. Alias for [`CanFrame::hal_id`].
+    pub fn id(&self) -> Id {
+        self.hal_id()
+    }
+
+    /// Returns the frame's data length code.
+    pub fn dlc(&self) -> usize {
+        match self {
+            CanFrame::DataFrame { dlc, .. } => *dlc as usize,
+            CanFrame::FdDataFrame { dlc, .. } => *dlc as usize,
+            CanFrame::RemoteFrame { dlc, .. } => *dlc as usize,
+            CanFrame::ErrorFrame { .. } => 0,
+        }
+    }
+
+    /// Returns the frame's data payload, truncated to its `dlc`. Empty for non-data frames.
+    pub fn data(&self) -> &[u8] {
+        match self {
+            CanFrame::DataFrame { dlc, data, .. } => &data[..*dlc as usize],
+            CanFrame::FdDataFrame { dlc, data, .. } => &data[..*dlc as usize],
+            _ => &[],
+        }
+    }
+
+    /// Returns the frame's `dlc`, capped to the classic `[0,8]` range the [`Frame`] trait
+    /// contract promises. [`CanFrame::FdDataFrame`] values with a larger `dlc` are capped rather
+    /// than exposed, so generic classic-CAN HAL drivers never see a length they can't handle.
+    fn classic_dlc(&self) -> usize {
+        self.dlc().min(8)
+    }
+}
+
+impl From<&CanFrame> for Id {
+    fn from(frame: &CanFrame) -> Self {
+        frame.hal_id()
+    }
+}
+
+impl Frame for CanFrame {
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        // the `Frame` contract requires `None` for a payload that doesn't fit, rather than the
+        // silent truncation `CanFrame::new_data_frame` performs for its own, more permissive API
+        if data.len() > 8 {
+            return None;
+        }
+
+        let (can_id, id_type) = match id.into() {
+            Id::Standard(id) => (id.as_raw() as u32, IdType::Standard),
+            Id::Extended(id) => (id.as_raw(), IdType::Extended),
+        };
+        Some(CanFrame::new_data_frame(can_id, id_type, data))
+    }
+
+    fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        // the `Frame` contract requires `None` for an invalid dlc, rather than the silent
+        // clamping `CanFrame::new_remote_frame` performs for its own, more permissive API
+        if dlc > 8 {
+            return None;
+        }
+
+        let (can_id, id_type) = match id.into() {
+            Id::Standard(id) => (id.as_raw() as u32, IdType::Standard),
+            Id::Extended(id) => (id.as_raw(), IdType::Extended),
+        };
+        Some(CanFrame::new_remote_frame(can_id, id_type, dlc as u8))
+    }
+
+    fn is_extended(&self) -> bool {
+        CanFrame::is_extended(self)
+    }
+
+    fn is_remote_frame(&self) -> bool {
+        CanFrame::is_remote_frame(self)
+    }
+
+    fn id(&self) -> Id {
+        CanFrame::id(self)
+    }
+
+    fn dlc(&self) -> usize {
+        self.classic_dlc()
+    }
+
+    fn data(&self) -> &[u8] {
+        &CanFrame::data(self)[..self.classic_dlc()]
+    }
+}
@@ -0,0 +1,11 @@
+//! `cantypes` provides types and traits for representing and filtering CAN frames and CAN-IDs,
+//! independent of any particular CAN driver or platform.
+
+pub mod constants;
+pub mod filter;
+pub mod frame;
+#[cfg(feature = "embedded-hal")]
+pub mod hal;
+
+pub use constants::*;
+pub use frame::{CanFrame, IdType};